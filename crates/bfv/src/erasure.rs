@@ -0,0 +1,233 @@
+//! Polynomial-based erasure coding for byte blobs.
+//!
+//! Bytes are packed into the coefficients of a degree-`N` polynomial over the
+//! plaintext ring, which is then evaluated at `M > N` domain points using
+//! [`Poly::evaluate_many`]. Because the packed polynomial is fully determined
+//! by any `N` of those `M` evaluations, this is a Reed-Solomon-style erasure
+//! code: losing up to `M - N` evaluations still allows the original bytes to
+//! be recovered via [`Context::interpolate`]. The code is not systematic: the
+//! evaluations are not the packed bytes themselves.
+
+use crate::parameters::BfvParameters;
+use crate::plaintext::{Encoding, Plaintext};
+use crate::traits::{Decoder, Encoder};
+use math::rq::{Context, Poly, Representation};
+use ndarray::{Array1, Array2};
+use std::rc::Rc;
+
+/// Pack `bytes` into the coefficients of a degree-`degree` polynomial, one
+/// byte per coefficient in little-endian order.
+///
+/// The first coefficient stores `bytes.len()` rather than payload, so that
+/// [`unpack_bytes`] can recover the exact length instead of having to guess
+/// it from trailing zero coefficients (which are indistinguishable from a
+/// real trailing `0x00` byte).
+///
+/// `modulus` must be large enough to hold a byte (`> 255`) and a length
+/// prefix up to `degree` (`> degree as u64`) without wrapping; a plaintext
+/// modulus this small would otherwise reduce bytes or the length prefix
+/// mod `modulus` and corrupt the payload without any indication, so this is
+/// rejected instead.
+pub fn bytes_to_polynomial(bytes: &[u8], degree: usize, modulus: u64) -> Result<Vec<u64>, String> {
+	if modulus <= 255 {
+		return Err(format!(
+			"The plaintext modulus {modulus} is too small to hold a byte without wrapping"
+		));
+	}
+	if modulus <= degree as u64 {
+		return Err(format!(
+			"The plaintext modulus {modulus} is too small to hold a length prefix up to {degree} without wrapping"
+		));
+	}
+	if bytes.len() + 1 > degree {
+		return Err(format!(
+			"{} bytes do not fit in a degree-{} polynomial",
+			bytes.len(),
+			degree
+		));
+	}
+	let mut coefficients = vec![0u64; degree];
+	coefficients[0] = bytes.len() as u64;
+	for (c, &b) in coefficients[1..].iter_mut().zip(bytes) {
+		*c = b as u64;
+	}
+	Ok(coefficients)
+}
+
+/// Unpack the coefficients of a polynomial produced by [`bytes_to_polynomial`]
+/// back into bytes, using the length stored in the first coefficient rather
+/// than inferring it from trailing zeros.
+fn unpack_bytes(coefficients: &[u64]) -> Result<Vec<u8>, String> {
+	let len = *coefficients
+		.first()
+		.ok_or_else(|| "The polynomial has no coefficients".to_string())? as usize;
+	coefficients
+		.get(1..=len)
+		.map(|bytes| bytes.iter().map(|&c| c as u8).collect())
+		.ok_or_else(|| "The stored length exceeds the polynomial's degree".to_string())
+}
+
+impl Encoder<&[u8]> for Plaintext {
+	type Error = String;
+
+	/// Pack `value` into the coefficients of a [`Plaintext`], length-prefixed
+	/// so it can be recovered exactly, regardless of trailing zero bytes.
+	fn try_encode(value: &[u8], encoding: Encoding, par: &Rc<BfvParameters>) -> Result<Self, Self::Error> {
+		let coefficients = bytes_to_polynomial(value, par.degree, par.plaintext.modulus())?;
+		<Plaintext as Encoder<&[u64]>>::try_encode(&coefficients, encoding, par)
+	}
+}
+
+impl Decoder for Vec<u8> {
+	type Error = String;
+
+	/// Recover the bytes packed into `a` by the `Encoder<&[u8]>` impl above.
+	fn try_decode<E>(a: &Plaintext, encoding: E) -> Result<Self, Self::Error>
+	where
+		E: Into<Option<Encoding>>,
+	{
+		let coefficients = <Vec<u64> as Decoder>::try_decode(a, encoding)?;
+		unpack_bytes(&coefficients)
+	}
+}
+
+/// A polynomial-based, non-systematic erasure code for a [`Plaintext`].
+///
+/// The plaintext's degree-`N` polynomial is evaluated at `domain.len()`
+/// points, `M > N`; any `N` of those `M` evaluations are enough to
+/// reconstruct the plaintext, and therefore the bytes packed into it, via
+/// [`ErasureCoded::reconstruct`].
+#[derive(Debug, Clone)]
+pub struct ErasureCoded {
+	/// The context the underlying plaintext polynomial lives in.
+	pub ctx: Rc<Context>,
+	/// The `M` domain points the polynomial was evaluated at.
+	pub domain: Vec<u64>,
+	/// The evaluation of the polynomial at each point in `domain`.
+	pub evaluations: Array2<u64>,
+}
+
+impl ErasureCoded {
+	/// Evaluate `value`, packed as a [`Plaintext`] would be, at `2 * par.degree`
+	/// domain points, so up to `par.degree` of them may be lost without losing
+	/// `value` itself.
+	///
+	/// The resulting shares are redundancy for `value`; to get an encryptable
+	/// [`Plaintext`] for `value` itself, encode it directly with
+	/// `Plaintext::try_encode`.
+	///
+	/// Fails if `par`'s plaintext modulus isn't larger than `2 * par.degree`,
+	/// since the domain points would then collide modulo it and
+	/// [`Self::reconstruct`] could never interpolate them back.
+	pub fn encode(value: &[u8], par: &Rc<BfvParameters>) -> Result<Self, String> {
+		let modulus = par.plaintext.modulus();
+		let ctx = Rc::new(Context::new(&[modulus], par.degree)?);
+
+		let coefficients = bytes_to_polynomial(value, par.degree, modulus)?;
+		let mut raw = Array2::<u64>::zeros((1, par.degree));
+		raw.row_mut(0).assign(&Array1::from(coefficients));
+		let poly = Poly {
+			ctx: ctx.clone(),
+			representation: Representation::PowerBasis,
+			allow_variable_time_computations: false,
+			coefficients: raw,
+			coefficients_shoup: None,
+		};
+
+		let domain: Vec<u64> = (0..2 * par.degree as u64).collect();
+		let evaluations = poly.evaluate_many(&domain)?;
+
+		Ok(ErasureCoded {
+			ctx,
+			domain,
+			evaluations,
+		})
+	}
+
+	/// Recover the original bytes from exactly `ctx.degree` evaluations and
+	/// their corresponding domain points, taken from any subset of the `M`
+	/// evaluations produced by [`ErasureCoded::encode`].
+	pub fn reconstruct(
+		ctx: &Rc<Context>,
+		domain: &[u64],
+		evaluations: &Array2<u64>,
+	) -> Result<Vec<u8>, String> {
+		if domain.len() != ctx.degree {
+			return Err(format!(
+				"Exactly {} evaluations are required to reconstruct the polynomial, found {}",
+				ctx.degree,
+				domain.len()
+			));
+		}
+
+		let poly = Context::interpolate(ctx, domain, evaluations)?;
+		unpack_bytes(poly.coefficients.row(0).as_slice().unwrap())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ndarray::s;
+
+	// `BfvParameters`/`Plaintext` aren't part of this snapshot, so these
+	// tests exercise the length-prefix packing and the underlying
+	// `math::rq` erasure-coding machinery directly, the same way
+	// `Encoder<&[u8]>`/`ErasureCoded` build on them above.
+
+	#[test]
+	fn test_bytes_to_polynomial_roundtrip() -> Result<(), String> {
+		let degree = 8;
+		let modulus = 65537;
+		for payload in [&b""[..], &b"\0"[..], &b"hi\0"[..], &b"\0\0\0"[..], &b"seventy"[..]] {
+			let coefficients = bytes_to_polynomial(payload, degree, modulus)?;
+			assert_eq!(coefficients.len(), degree);
+			assert_eq!(unpack_bytes(&coefficients)?, payload);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn test_bytes_to_polynomial_rejects_unsafe_moduli() {
+		assert!(bytes_to_polynomial(b"hi", 8, 255).is_err());
+		assert!(bytes_to_polynomial(b"hi", 8, 8).is_err());
+		assert!(bytes_to_polynomial(b"hi", 8, 65537).is_ok());
+	}
+
+	#[test]
+	fn test_bytes_to_polynomial_rejects_oversized_payload() {
+		assert!(bytes_to_polynomial(&[0u8; 8], 8, 65537).is_err());
+		assert!(bytes_to_polynomial(&[0u8; 7], 8, 65537).is_ok());
+	}
+
+	#[test]
+	fn test_erasure_coding_roundtrip_survives_lost_evaluations() -> Result<(), String> {
+		let degree = 8;
+		let modulus = 4611686018282684417u64;
+		let ctx = Rc::new(Context::new(&[modulus], degree)?);
+
+		let value = b"lost";
+		let coefficients = bytes_to_polynomial(value, degree, modulus)?;
+		let mut raw = Array2::<u64>::zeros((1, degree));
+		raw.row_mut(0).assign(&Array1::from(coefficients));
+		let poly = Poly {
+			ctx: ctx.clone(),
+			representation: Representation::PowerBasis,
+			allow_variable_time_computations: false,
+			coefficients: raw,
+			coefficients_shoup: None,
+		};
+
+		let domain: Vec<u64> = (0..2 * degree as u64).collect();
+		let evaluations = poly.evaluate_many(&domain)?;
+
+		// Drop the first half of the evaluations; the remaining `degree` are
+		// still enough to reconstruct.
+		let surviving_domain = domain[degree..].to_vec();
+		let surviving_evaluations = evaluations.slice(s![.., degree..]).to_owned();
+
+		let recovered = ErasureCoded::reconstruct(&ctx, &surviving_domain, &surviving_evaluations)?;
+		assert_eq!(recovered, value);
+		Ok(())
+	}
+}
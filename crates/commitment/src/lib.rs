@@ -0,0 +1,162 @@
+#![warn(missing_docs, unused_imports)]
+
+//! KZG polynomial commitments for `Poly` and `Plaintext` coefficients.
+//!
+//! This lets a party commit to a [`math::rq::Poly`] (or the coefficient
+//! vector of a BFV `Plaintext`) and later open the commitment at a chosen
+//! point, so a verifier can check that a value really corresponds to the
+//! committed input without seeing the rest of it. Coefficients live in RNS
+//! residue rings rather than in a pairing-friendly scalar field, so they are
+//! first CRT-reconstructed to integers (reusing the same reconstruction path
+//! as [`math::rq::Scaler`]) and reduced modulo the scalar field of the curve
+//! used here, BLS12-381.
+//!
+//! The maximum polynomial degree a [`PowersOfTau`] setup supports is fixed at
+//! setup time and must be at least `Context::degree - 1` for any context
+//! whose polynomials will be committed to.
+
+mod scalar;
+mod setup;
+
+pub use scalar::poly_to_scalars;
+pub use setup::PowersOfTau;
+
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+/// A commitment to a polynomial, the size of a single group element
+/// regardless of the polynomial's degree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commitment(G1Projective);
+
+/// A proof that a committed polynomial evaluates to a claimed value at a
+/// chosen point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opening {
+	/// The claimed evaluation `p(z)`.
+	pub y: Scalar,
+	/// The commitment to the quotient polynomial `(p(X) - y) / (X - z)`.
+	pi: G1Projective,
+}
+
+impl PowersOfTau {
+	/// Commit to the polynomial with (low-to-high) coefficients
+	/// `coefficients`.
+	///
+	/// `coefficients.len()` must not exceed `self.max_degree() + 1`.
+	pub fn commit(&self, coefficients: &[Scalar]) -> Result<Commitment, String> {
+		if coefficients.len() > self.max_degree() + 1 {
+			return Err(format!(
+				"The polynomial has degree {}, larger than the {} supported by this setup",
+				coefficients.len() - 1,
+				self.max_degree()
+			));
+		}
+		Ok(Commitment(self.evaluate_in_g1(coefficients)))
+	}
+
+	/// Open the commitment to `coefficients` at `z`, producing the claimed
+	/// value `p(z)` together with a proof of correctness.
+	pub fn open(&self, coefficients: &[Scalar], z: Scalar) -> Result<Opening, String> {
+		let y = evaluate(coefficients, z);
+		let quotient = synthetic_division(coefficients, z, y);
+		let pi = self.commit(&quotient)?.0;
+		Ok(Opening { y, pi })
+	}
+
+	/// Verify that `commitment` opens to `opening.y` at `z`.
+	///
+	/// Checks the pairing equation
+	/// `e(C - y·g, g2) == e(π, g2^τ - z·g2)`.
+	pub fn verify(&self, commitment: &Commitment, z: Scalar, opening: &Opening) -> bool {
+		let lhs = commitment.0 - self.g1_generator() * opening.y;
+		let rhs = self.g2_tau() - self.g2_generator() * z;
+
+		pairing(&G1Affine::from(lhs), &G2Affine::from(self.g2_generator()))
+			== pairing(&G1Affine::from(opening.pi), &G2Affine::from(rhs))
+	}
+}
+
+/// Evaluate the polynomial with (low-to-high) `coefficients` at `z`, using
+/// Horner's method.
+fn evaluate(coefficients: &[Scalar], z: Scalar) -> Scalar {
+	coefficients
+		.iter()
+		.rev()
+		.fold(Scalar::zero(), |acc, &c| acc * z + c)
+}
+
+/// Compute the quotient `q(X) = (p(X) - y) / (X - z)` by synthetic division,
+/// given that `p(z) == y` (so the division is exact).
+fn synthetic_division(coefficients: &[Scalar], z: Scalar, y: Scalar) -> Vec<Scalar> {
+	let mut shifted = coefficients.to_vec();
+	if let Some(c0) = shifted.first_mut() {
+		*c0 -= y;
+	}
+
+	let n = shifted.len();
+	let mut quotient = vec![Scalar::zero(); n.saturating_sub(1)];
+	let mut carry = Scalar::zero();
+	for i in (0..n.saturating_sub(1)).rev() {
+		carry = shifted[i + 1] + carry * z;
+		quotient[i] = carry;
+	}
+	quotient
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_commit_open_verify_roundtrip() -> Result<(), String> {
+		let tau = Scalar::from(424242u64);
+		let setup = PowersOfTau::setup(8, tau);
+
+		let coefficients: Vec<Scalar> = (1..=5).map(Scalar::from).collect();
+		let commitment = setup.commit(&coefficients)?;
+
+		let z = Scalar::from(7u64);
+		let opening = setup.open(&coefficients, z)?;
+		assert_eq!(opening.y, evaluate(&coefficients, z));
+		assert!(setup.verify(&commitment, z, &opening));
+
+		let wrong_opening = Opening {
+			y: opening.y + Scalar::one(),
+			..opening
+		};
+		assert!(!setup.verify(&commitment, z, &wrong_opening));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_commit_to_a_poly() -> Result<(), String> {
+		use math::rq::{Context, Poly, Representation};
+		use std::rc::Rc;
+
+		static Q: &[u64; 2] = &[4611686018282684417, 4611686018326724609];
+		let ctx = Rc::new(Context::new(Q, 8)?);
+		let poly = Poly::random(&ctx, Representation::PowerBasis);
+
+		let coefficients = poly_to_scalars(&poly);
+		assert_eq!(coefficients.len(), ctx.degree);
+
+		let tau = Scalar::from(13u64);
+		let setup = PowersOfTau::setup(ctx.degree - 1, tau);
+		let commitment = setup.commit(&coefficients)?;
+
+		let z = Scalar::from(5u64);
+		let opening = setup.open(&coefficients, z)?;
+		assert_eq!(opening.y, evaluate(&coefficients, z));
+		assert!(setup.verify(&commitment, z, &opening));
+
+		// Committing to the polynomial's NTT representation must lift the
+		// same underlying coefficients, since `poly_to_scalars` converts back
+		// to `PowerBasis` internally.
+		let mut poly_ntt = poly.clone();
+		poly_ntt.change_representation(Representation::Ntt);
+		assert_eq!(poly_to_scalars(&poly_ntt), coefficients);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,28 @@
+//! Lifting RNS polynomial coefficients into the BLS12-381 scalar field.
+
+use bls12_381::Scalar;
+use math::rq::Poly;
+use num_bigint::BigUint;
+
+/// CRT-reconstruct `poly`'s coefficients to integers (the same
+/// reconstruction [`math::rq::Scaler`] relies on) and reduce each one modulo
+/// the BLS12-381 scalar field, in coefficient (`PowerBasis`) order.
+pub fn poly_to_scalars(poly: &Poly) -> Vec<Scalar> {
+	let mut poly = poly.clone();
+	if poly.representation == math::rq::Representation::Ntt {
+		poly.change_representation(math::rq::Representation::PowerBasis);
+	}
+
+	Vec::<BigUint>::from(&poly)
+		.iter()
+		.map(biguint_to_scalar)
+		.collect()
+}
+
+/// Reduce `v` modulo the scalar field order and convert it to a [`Scalar`],
+/// via Horner's method over `v`'s big-endian bytes.
+fn biguint_to_scalar(v: &BigUint) -> Scalar {
+	v.to_bytes_be().iter().fold(Scalar::zero(), |acc, &byte| {
+		acc * Scalar::from(256u64) + Scalar::from(byte as u64)
+	})
+}
@@ -0,0 +1,65 @@
+//! Trusted setup: the powers of a secret `tau` in `G1`, plus `g2` and
+//! `g2^tau` in `G2`, as used by the KZG commitment scheme.
+
+use bls12_381::{G1Projective, G2Projective, Scalar};
+
+/// The public parameters of a KZG trusted setup: `{g1, g1^tau, ..., g1^(tau^d)}`
+/// and `{g2, g2^tau}`.
+///
+/// Producing these from a `tau` known to any single party (as
+/// [`PowersOfTau::setup`] does) is only safe for tests: in production `tau`
+/// must be "toxic waste", generated and immediately discarded by an MPC
+/// ceremony that no participant can fully reconstruct.
+#[derive(Debug, Clone)]
+pub struct PowersOfTau {
+	g1_powers: Vec<G1Projective>,
+	g2: G2Projective,
+	g2_tau: G2Projective,
+}
+
+impl PowersOfTau {
+	/// Generate the powers of `tau` up to degree `max_degree`.
+	///
+	/// See the type-level documentation: this takes `tau` in the clear and
+	/// is only appropriate for tests.
+	pub fn setup(max_degree: usize, tau: Scalar) -> Self {
+		let mut g1_powers = Vec::with_capacity(max_degree + 1);
+		let mut power = Scalar::one();
+		for _ in 0..=max_degree {
+			g1_powers.push(G1Projective::generator() * power);
+			power *= tau;
+		}
+
+		Self {
+			g1_powers,
+			g2: G2Projective::generator(),
+			g2_tau: G2Projective::generator() * tau,
+		}
+	}
+
+	/// The largest polynomial degree this setup can commit to.
+	pub fn max_degree(&self) -> usize {
+		self.g1_powers.len() - 1
+	}
+
+	/// `sum_i coefficients[i] * g1^(tau^i)`.
+	pub(crate) fn evaluate_in_g1(&self, coefficients: &[Scalar]) -> G1Projective {
+		coefficients
+			.iter()
+			.zip(&self.g1_powers)
+			.map(|(&c, &power)| power * c)
+			.fold(G1Projective::identity(), |acc, term| acc + term)
+	}
+
+	pub(crate) fn g1_generator(&self) -> G1Projective {
+		self.g1_powers[0]
+	}
+
+	pub(crate) fn g2_generator(&self) -> G2Projective {
+		self.g2
+	}
+
+	pub(crate) fn g2_tau(&self) -> G2Projective {
+		self.g2_tau
+	}
+}
@@ -0,0 +1,198 @@
+//! Arbitrary-domain multipoint evaluation and Lagrange interpolation.
+//!
+//! The NTT only ever evaluates a polynomial at (powers of) a fixed root of
+//! unity. These routines evaluate and interpolate at caller-chosen domain
+//! points instead, independently in each residue ring, which is what a
+//! Reed-Solomon-style erasure code over a polynomial's coefficients needs.
+
+use super::{Context, Poly, Representation};
+use itertools::izip;
+use ndarray::Array2;
+use std::rc::Rc;
+
+impl Poly {
+	/// Evaluate every RNS residue of this polynomial, independently, at each
+	/// point in `points`, using Horner's method.
+	///
+	/// Returns an array of shape `(ctx.q.len(), points.len())`. Fails if any
+	/// modulus of `ctx` is not large enough to keep `points` distinct modulo
+	/// it, since [`Context::interpolate`] could then no longer recover the
+	/// polynomial from these values.
+	pub fn evaluate_many(&self, points: &[u64]) -> Result<Array2<u64>, String> {
+		check_domain_fits(&self.ctx.q, points.len())?;
+
+		let mut p = self.clone();
+		if p.representation == Representation::Ntt {
+			p.change_representation(Representation::PowerBasis);
+		}
+
+		let mut values = Array2::<u64>::zeros((p.ctx.q.len(), points.len()));
+		izip!(values.outer_iter_mut(), p.coefficients.outer_iter(), &p.ctx.q).for_each(
+			|(mut out_row, coefficients, &modulus)| {
+				let coefficients = coefficients.as_slice().unwrap();
+				for (out, &x) in out_row.iter_mut().zip(points) {
+					*out = horner(coefficients, x, modulus);
+				}
+			},
+		);
+		Ok(values)
+	}
+}
+
+impl Context {
+	/// Interpolate a polynomial from its values at `domain`, independently in
+	/// each residue ring, via Lagrange interpolation.
+	///
+	/// `domain.len()` must equal both `self.degree` and the number of columns
+	/// of `values`, and `values` must have `self.q.len()` rows, one per
+	/// modulus of `self`.
+	///
+	/// Fails if any modulus of `self` is not large enough to keep `domain`'s
+	/// points distinct modulo it, since two points colliding modulo a
+	/// modulus make that residue's Lagrange denominator zero.
+	pub fn interpolate(self: &Rc<Self>, domain: &[u64], values: &Array2<u64>) -> Result<Poly, String> {
+		assert_eq!(domain.len(), self.degree);
+		assert_eq!(values.shape(), [self.q.len(), domain.len()]);
+		check_domain_fits(&self.q, domain.len())?;
+
+		let mut coefficients = Array2::<u64>::zeros((self.q.len(), self.degree));
+		for (mut out_row, y, &modulus) in izip!(coefficients.outer_iter_mut(), values.outer_iter(), &self.q) {
+			let row = lagrange_interpolate(domain, y.as_slice().unwrap(), modulus)?;
+			out_row.assign(&ndarray::Array1::from(row));
+		}
+
+		Ok(Poly {
+			ctx: self.clone(),
+			representation: Representation::PowerBasis,
+			allow_variable_time_computations: false,
+			coefficients,
+			coefficients_shoup: None,
+		})
+	}
+}
+
+/// Check that `len` points can stay pairwise distinct modulo every modulus in
+/// `moduli`, since a modulus no larger than `len` can't hold that many
+/// distinct residues.
+fn check_domain_fits(moduli: &[u64], len: usize) -> Result<(), String> {
+	if let Some(&modulus) = moduli.iter().find(|&&modulus| modulus <= len as u64) {
+		return Err(format!(
+			"{len} domain points may collide modulo the modulus {modulus}"
+		));
+	}
+	Ok(())
+}
+
+/// Evaluate the polynomial with (low-to-high) `coefficients` at `x`, modulo
+/// `modulus`, using Horner's method.
+fn horner(coefficients: &[u64], x: u64, modulus: u64) -> u64 {
+	coefficients
+		.iter()
+		.rev()
+		.fold(0u64, |acc, &c| addmod(mulmod(acc, x, modulus), c, modulus))
+}
+
+/// Lagrange-interpolate the unique degree-`< domain.len()` polynomial that
+/// takes value `y[i]` at `domain[i]`, modulo `modulus`, and return its
+/// (low-to-high) coefficients.
+///
+/// Fails if two points of `domain` collide modulo `modulus`, since the
+/// Lagrange denominator for that point is then zero and has no inverse.
+fn lagrange_interpolate(domain: &[u64], y: &[u64], modulus: u64) -> Result<Vec<u64>, String> {
+	let n = domain.len();
+	let mut coefficients = vec![0u64; n];
+
+	for j in 0..n {
+		// Numerator: the coefficients of prod_{k != j} (X - domain[k]).
+		let mut basis = vec![0u64; n];
+		basis[0] = 1;
+		let mut degree = 0;
+		let mut denominator = 1u64;
+		for (k, &xk) in domain.iter().enumerate() {
+			if k == j {
+				continue;
+			}
+			for i in (1..=degree + 1).rev() {
+				basis[i] = submod(basis[i - 1], mulmod(basis[i], xk, modulus), modulus);
+			}
+			basis[0] = submod(0, mulmod(basis[0], xk, modulus), modulus);
+			degree += 1;
+			denominator = mulmod(denominator, submod(domain[j], xk, modulus), modulus);
+		}
+
+		let scale = mulmod(y[j], inv_mod(denominator, modulus)?, modulus);
+		for (c, b) in coefficients.iter_mut().zip(&basis) {
+			*c = addmod(*c, mulmod(scale, *b, modulus), modulus);
+		}
+	}
+
+	Ok(coefficients)
+}
+
+fn addmod(a: u64, b: u64, modulus: u64) -> u64 {
+	((a as u128 + b as u128) % modulus as u128) as u64
+}
+
+fn submod(a: u64, b: u64, modulus: u64) -> u64 {
+	addmod(a, modulus - (b % modulus), modulus)
+}
+
+fn mulmod(a: u64, b: u64, modulus: u64) -> u64 {
+	((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+/// The inverse of `a` modulo `modulus`, via the extended Euclidean algorithm.
+///
+/// Fails if `a` is `0` modulo `modulus`, which has no inverse.
+fn inv_mod(a: u64, modulus: u64) -> Result<u64, String> {
+	if a % modulus == 0 {
+		return Err(format!("{a} has no inverse modulo {modulus}"));
+	}
+
+	let (mut old_r, mut r) = (a as i128, modulus as i128);
+	let (mut old_s, mut s) = (1i128, 0i128);
+	while r != 0 {
+		let quotient = old_r / r;
+		(old_r, r) = (r, old_r - quotient * r);
+		(old_s, s) = (s, old_s - quotient * s);
+	}
+	Ok((((old_s % modulus as i128) + modulus as i128) % modulus as i128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{check_domain_fits, inv_mod, lagrange_interpolate, Context, Poly, Representation};
+	use std::rc::Rc;
+
+	static Q: &[u64; 2] = &[4611686018282684417, 4611686018326724609];
+
+	#[test]
+	fn test_evaluate_many_and_interpolate_roundtrip() -> Result<(), String> {
+		let ctx = Rc::new(Context::new(Q, 8)?);
+		let domain: Vec<u64> = (1..=8).collect();
+
+		for _ in 0..20 {
+			let poly = Poly::random(&ctx, Representation::PowerBasis);
+			let values = poly.evaluate_many(&domain)?;
+			let interpolated = Context::interpolate(&ctx, &domain, &values)?;
+			assert_eq!(interpolated.coefficients, poly.coefficients);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_check_domain_fits_rejects_a_too_small_modulus() {
+		assert!(check_domain_fits(&[17, 1000], 8).is_ok());
+		assert!(check_domain_fits(&[17, 1000], 17).is_err());
+	}
+
+	#[test]
+	fn test_inv_mod_and_lagrange_interpolate_reject_a_collision() {
+		assert!(inv_mod(3, 17).is_ok());
+		assert!(inv_mod(0, 17).is_err());
+
+		// Two domain points, 2 and 9, collide modulo 7.
+		assert!(lagrange_interpolate(&[2, 9], &[1, 1], 7).is_err());
+	}
+}
@@ -5,24 +5,89 @@
 use super::{Context, Poly, Representation};
 use crate::rns::{RnsScaler, ScalingFactor};
 use itertools::izip;
+use ndarray::parallel::prelude::*;
 use ndarray::{s, Array2, Axis};
 use std::rc::Rc;
 
 /// Context extender.
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+///
+/// The backward/forward NTT conversions are parallelized one modulus (row)
+/// at a time, since each row's transform is already independent of the
+/// others, and the RNS-limb conversion between them one coefficient
+/// (column) at a time. A single large
+/// transform's butterfly passes are not themselves split across threads;
+/// row-level parallelism already saturates `num_threads` cores for the
+/// degree/modulus-count combinations this crate targets.
+#[derive(Default, Clone)]
 pub struct Scaler {
 	from: Rc<Context>,
 	to: Rc<Context>,
 	number_common_moduli: usize,
 	scaler: RnsScaler,
+	num_threads: usize,
+	// Built once in `new_with_threads` and reused by every `scale()` call;
+	// `None` when `num_threads == 1`, so parallel work dispatches onto
+	// rayon's ambient global pool instead of a dedicated one.
+	// `rayon::ThreadPool` has no meaningful `Debug`/`PartialEq`, so those
+	// traits below are implemented by hand, ignoring this field.
+	pool: Option<Rc<rayon::ThreadPool>>,
 }
 
+impl std::fmt::Debug for Scaler {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Scaler")
+			.field("from", &self.from)
+			.field("to", &self.to)
+			.field("number_common_moduli", &self.number_common_moduli)
+			.field("scaler", &self.scaler)
+			.field("num_threads", &self.num_threads)
+			.finish()
+	}
+}
+
+impl PartialEq for Scaler {
+	fn eq(&self, other: &Self) -> bool {
+		self.from == other.from
+			&& self.to == other.to
+			&& self.number_common_moduli == other.number_common_moduli
+			&& self.scaler == other.scaler
+			&& self.num_threads == other.num_threads
+	}
+}
+
+impl Eq for Scaler {}
+
 impl Scaler {
 	/// Create a scaler from a context `from` to a context `to`.
+	///
+	/// Builds no dedicated thread pool of its own, so `scale()` dispatches
+	/// its parallel work onto rayon's ambient global thread pool; call
+	/// [`Scaler::new_with_threads`] to size and reuse a dedicated pool
+	/// instead.
 	pub fn new(
 		from: &Rc<Context>,
 		to: &Rc<Context>,
 		factor: ScalingFactor,
+	) -> Result<Self, String> {
+		Self::new_with_threads(from, to, factor, 1)
+	}
+
+	/// Create a scaler from a context `from` to a context `to`, running the
+	/// parallel RNS-limb and NTT conversions on a dedicated pool of
+	/// `num_threads` threads, or on rayon's global pool when `num_threads`
+	/// is `0` or `1`.
+	///
+	/// Pass `1` to dispatch parallel work onto rayon's ambient global thread
+	/// pool instead of building a dedicated one, e.g. on targets where
+	/// spinning up an extra pool is not desirable. Any other `num_threads`
+	/// builds a dedicated pool once, here, and reuses it for every
+	/// subsequent `scale()` call on this `Scaler`. No value of
+	/// `num_threads` confines `scale()` to the calling thread.
+	pub fn new_with_threads(
+		from: &Rc<Context>,
+		to: &Rc<Context>,
+		factor: ScalingFactor,
+		num_threads: usize,
 	) -> Result<Self, String> {
 		let mut number_common_moduli = 0;
 		if factor.is_one {
@@ -36,14 +101,37 @@ impl Scaler {
 		}
 
 		let scaler = RnsScaler::new(&from.rns, &to.rns, factor);
+		let num_threads = num_threads.max(1);
+		let pool = if num_threads == 1 {
+			None
+		} else {
+			Some(Rc::new(
+				rayon::ThreadPoolBuilder::new()
+					.num_threads(num_threads)
+					.build()
+					.map_err(|e| e.to_string())?,
+			))
+		};
 
 		Ok(Self {
 			from: from.clone(),
 			to: to.clone(),
 			number_common_moduli,
 			scaler,
+			num_threads,
+			pool,
 		})
 	}
+
+	/// Run `f` on `self`'s cached thread pool, or directly on the calling
+	/// thread (so its parallel work falls onto rayon's ambient global pool)
+	/// when `self.num_threads == 1`.
+	fn run_pooled<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+		match &self.pool {
+			Some(pool) => pool.install(f),
+			None => f(),
+		}
+	}
 }
 
 impl Scaler {
@@ -60,58 +148,51 @@ impl Scaler {
 					.assign(&p.coefficients.slice(s![..self.number_common_moduli, ..]));
 			}
 
-			if p.representation == Representation::PowerBasis {
-				izip!(
-					new_coefficients
-						.slice_mut(s![self.number_common_moduli.., ..])
-						.axis_iter_mut(Axis(1)),
-					p.coefficients.axis_iter(Axis(1))
-				)
-				.for_each(|(mut new_column, column)| {
-					self.scaler
-						.scale(&column, &mut new_column, self.number_common_moduli, floor)
-				});
-			} else {
-				let mut p_coefficients_powerbasis = p.coefficients.clone();
-				// Backward NTT
-				if p.allow_variable_time_computations {
-					izip!(p_coefficients_powerbasis.outer_iter_mut(), &p.ctx.ops).for_each(
-						|(mut v, op)| unsafe { op.backward_vt(v.as_slice_mut().unwrap()) },
-					);
+			self.run_pooled(|| -> Result<(), String> {
+				if p.representation == Representation::PowerBasis {
+					self.convert_columns(&mut new_coefficients, &p.coefficients, floor);
 				} else {
-					izip!(p_coefficients_powerbasis.outer_iter_mut(), &p.ctx.ops)
-						.for_each(|(mut v, op)| op.backward(v.as_slice_mut().unwrap()));
-				}
-				// Conversion
-				izip!(
-					new_coefficients
-						.slice_mut(s![self.number_common_moduli.., ..])
-						.axis_iter_mut(Axis(1)),
-					p_coefficients_powerbasis.axis_iter(Axis(1))
-				)
-				.for_each(|(mut new_column, column)| {
-					self.scaler
-						.scale(&column, &mut new_column, self.number_common_moduli, floor)
-				});
-				// Forward NTT on the second half
-				if p.allow_variable_time_computations {
-					izip!(
+					let mut p_coefficients_powerbasis = p.coefficients.clone();
+					// Backward NTT, one modulus per row, independently.
+					if p.allow_variable_time_computations {
+						p_coefficients_powerbasis
+							.outer_iter_mut()
+							.into_par_iter()
+							.zip(p.ctx.ops.par_iter())
+							.for_each(|(mut v, op)| unsafe {
+								op.backward_vt(v.as_slice_mut().unwrap())
+							});
+					} else {
+						p_coefficients_powerbasis
+							.outer_iter_mut()
+							.into_par_iter()
+							.zip(p.ctx.ops.par_iter())
+							.for_each(|(mut v, op)| op.backward(v.as_slice_mut().unwrap()));
+					}
+					// Conversion
+					self.convert_columns(&mut new_coefficients, &p_coefficients_powerbasis, floor);
+					// Forward NTT on the second half, one modulus per row,
+					// independently.
+					if p.allow_variable_time_computations {
 						new_coefficients
 							.slice_mut(s![self.number_common_moduli.., ..])
-							.outer_iter_mut(),
-						&self.to.ops[self.number_common_moduli..]
-					)
-					.for_each(|(mut v, op)| unsafe { op.forward_vt(v.as_slice_mut().unwrap()) });
-				} else {
-					izip!(
+							.outer_iter_mut()
+							.into_par_iter()
+							.zip(self.to.ops[self.number_common_moduli..].par_iter())
+							.for_each(|(mut v, op)| unsafe {
+								op.forward_vt(v.as_slice_mut().unwrap())
+							});
+					} else {
 						new_coefficients
 							.slice_mut(s![self.number_common_moduli.., ..])
-							.outer_iter_mut(),
-						&self.to.ops[self.number_common_moduli..]
-					)
-					.for_each(|(mut v, op)| op.forward(v.as_slice_mut().unwrap()));
+							.outer_iter_mut()
+							.into_par_iter()
+							.zip(self.to.ops[self.number_common_moduli..].par_iter())
+							.for_each(|(mut v, op)| op.forward(v.as_slice_mut().unwrap()));
+					}
 				}
-			}
+				Ok(())
+			})?;
 
 			Ok(Poly {
 				ctx: self.to.clone(),
@@ -122,6 +203,22 @@ impl Scaler {
 			})
 		}
 	}
+
+	/// Convert `source`'s columns (one per coefficient, across all moduli of
+	/// `self.from`) into `destination`'s redundant moduli, independently and
+	/// in parallel: each column only touches its own disjoint slice of
+	/// `destination`, so this is safe to run concurrently.
+	fn convert_columns(&self, destination: &mut Array2<u64>, source: &Array2<u64>, floor: bool) {
+		destination
+			.slice_mut(s![self.number_common_moduli.., ..])
+			.axis_iter_mut(Axis(1))
+			.into_par_iter()
+			.zip(source.axis_iter(Axis(1)).into_par_iter())
+			.for_each(|(mut new_column, column)| {
+				self.scaler
+					.scale(&column, &mut new_column, self.number_common_moduli, floor)
+			});
+	}
 }
 
 #[cfg(test)]
@@ -156,38 +253,41 @@ mod tests {
 				let n = BigUint::from(*numerator);
 				let d = BigUint::from(*denominator);
 
-				let scaler = Scaler::new(&from, &to, ScalingFactor::new(&n, &d))?;
-
-				for _ in 0..ntests {
-					let mut poly = Poly::random(&from, Representation::PowerBasis);
-					let poly_biguint = Vec::<BigUint>::from(&poly);
-
-					let scaled_poly = scaler.scale(&poly, true)?;
-					let scaled_biguint = Vec::<BigUint>::from(&scaled_poly);
-
-					let expected = poly_biguint
-						.iter()
-						.map(|i| {
-							if i >= &(from.modulus() >> 1usize) {
-								to.modulus()
-									- (&(&(from.modulus() - i) * &n + &d - 1u64) / &d)
-										% to.modulus()
-							} else {
-								((i * &n) / &d) % to.modulus()
-							}
-						})
-						.collect_vec();
-					assert_eq!(expected, scaled_biguint);
-
-					poly.change_representation(Representation::Ntt);
-					let mut scaled_poly = scaler.scale(&poly, true)?;
-					scaled_poly.change_representation(Representation::PowerBasis);
-					let scaled_biguint = Vec::<BigUint>::from(&scaled_poly);
-					assert_eq!(expected, scaled_biguint);
+				for num_threads in [1, rayon::current_num_threads()] {
+					let scaler =
+						Scaler::new_with_threads(&from, &to, ScalingFactor::new(&n, &d), num_threads)?;
+
+					for _ in 0..ntests {
+						let mut poly = Poly::random(&from, Representation::PowerBasis);
+						let poly_biguint = Vec::<BigUint>::from(&poly);
+
+						let scaled_poly = scaler.scale(&poly, true)?;
+						let scaled_biguint = Vec::<BigUint>::from(&scaled_poly);
+
+						let expected = poly_biguint
+							.iter()
+							.map(|i| {
+								if i >= &(from.modulus() >> 1usize) {
+									to.modulus()
+										- (&(&(from.modulus() - i) * &n + &d - 1u64) / &d)
+											% to.modulus()
+								} else {
+									((i * &n) / &d) % to.modulus()
+								}
+							})
+							.collect_vec();
+						assert_eq!(expected, scaled_biguint);
+
+						poly.change_representation(Representation::Ntt);
+						let mut scaled_poly = scaler.scale(&poly, true)?;
+						scaled_poly.change_representation(Representation::PowerBasis);
+						let scaled_biguint = Vec::<BigUint>::from(&scaled_poly);
+						assert_eq!(expected, scaled_biguint);
+					}
 				}
 			}
 		}
 
 		Ok(())
 	}
-}
\ No newline at end of file
+}